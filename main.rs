@@ -1,14 +1,24 @@
 // Import required external packages
 // Rayon is for parallel mapping
 extern crate rayon;
+// Serde is for (de)serializing the model state for checkpointing
+extern crate serde;
+// rmp_serde is the compact binary (msgpack) format we checkpoint into
+extern crate rmp_serde;
 
 // Use required packages and standard library traits
 // For concurrency
 use std::thread;
 use std::thread::*;
+// For efficient blocking coordination instead of busy-waiting
+use std::sync::{Arc, Mutex, Condvar};
+// For cooperative cancellation of the candidate-k search
+use std::sync::atomic::{AtomicUsize, Ordering};
 // For parallel mapping
 use rayon::prelude;
 use rayon::prelude::*;
+// Derive macros for (de)serialization of the checkpoint state
+use serde::{Serialize, Deserialize};
 
 // Trait for SCKM Model itself
 pub trait SCKMModel {
@@ -16,29 +26,48 @@ pub trait SCKMModel {
   // Training must be a method that takes the eta hyperparameter
   // Training must return the Some(Trained) or None if it fails
   // Its work is stored in the self.result vector of cluster IDs
-  fn train(&mut self, eta: u32) -> option<Trained>,
+  // Returns Ok(Trained) on completion, or a typed SckmError otherwise
+  fn train(&mut self, eta: u32) -> Result<Trained, SckmError>,
+  // Parallel "search mode" alternative to train: discovers num_centers by
+  // evaluating candidate center counts concurrently and task-first, rather
+  // than the sequential discovery that train performs. Same Result contract
+  fn train_search(&mut self, eta: u32) -> Result<Trained, SckmError>,
   // Checks if a and b are in the same cluster
   // Takes a and b (points in a boolean space) as Vectors of booleans
-  // Returns Some(ConnectEnum) if self.trained is DoneEnum::done, otherwise None
+  // Returns Ok(ConnectEnum) once training is done, else a typed SckmError
+  // (NotTrained if train has not completed or centers are still None)
   // Its work is stored in the return, and must not mutate self
   // Note that a and b are Vec<bool> not LabelBoolPoint
   // This is because we do not use label information
-  fn same_cluster(&self, a: Vec<bool>, b: Vec<bool>) -> option<ConnectEnum>,
+  fn same_cluster(&self, a: Vec<bool>, b: Vec<bool>) -> Result<ConnectEnum, SckmError>,
   // This is used to update the SCKM.data value
   // Do not do this directly
   // This will automatically await the SCKM.trained TaskState
   // It will also set the SCKM.trained TaskState
   // Note that it calls SCKM::new, and then passes on the fields
-  fn update_data(&mut self, new_data: Vec<LabelBoolPoint>)
+  // This variant blocks the caller on a condvar until training is idle
+  fn update_data(&mut self, new_data: Vec<LabelBoolPoint>),
+  // Non-blocking sibling of update_data
+  // Returns Err with the live TaskState if a training run is in flight,
+  // otherwise performs the cold rebuild and returns Ok
+  fn try_update_data(&mut self, new_data: Vec<LabelBoolPoint>) -> Result<(), TaskState>,
+  // Warm-start variant: keeps the learned centers as the initialization and
+  // settles them over a bounded number of mini-batch iterations rather than
+  // throwing everything away and cold retraining via update_data.
+  // eta controls when a new point is far enough from every center to spawn
+  // its own center versus being absorbed into the nearest one
+  fn update_data_incremental(&mut self, new_data: Vec<LabelBoolPoint>, eta: u32)
 }
 
 // Trait for the constructor of an SCKM Model
 pub trait SCKMModelConstructor {
-  // The constructor takes the data and returns a Model
+  // The constructor takes the data and the distance metric, returns a Model
   // Note that the hyperparameter eta is given at training
-  fn new(given_data: Vec<LabelBoolPoint>) -> SCKM
+  fn new(given_data: Vec<LabelBoolPoint>, metric: MetricKind) -> SCKM
 }
 
+// The full model state, serialized verbatim for checkpoint/resume
+#[derive(Serialize, Deserialize)]
 pub struct SCKM {
   // Vector of points in a boolean space, use LabelBoolPoint struct
   data: Vec<LabelBoolPoint>,
@@ -46,19 +75,113 @@ pub struct SCKM {
   result: Vec<option<BoolPoint>>,
   // Number of cluster centers, None if not yet found, and the checking job
   num_centers: JobU8,
-  // Is the training done, ready, or pending
-  trained: TaskState
+  // Which training_iteration we are on, advanced by each pure step
+  // A resumed model picks up here rather than restarting from zero
+  iteration: u32,
+  // The boolean distance metric used for both training and querying
+  // Chosen at new and captured in the checkpoint so a resume can reject a
+  // mismatched metric rather than silently changing the model's geometry
+  metric: MetricKind,
+  // Is the training done, ready, pending, or paused
+  // This is the serialized snapshot of the state; the live state lives
+  // behind self.signal so waiters can be parked and woken efficiently
+  trained: TaskState,
+  // Observable per-k progress of the parallel center-count search
+  // Index k-1 holds the JobU8 for candidate k; empty until a search has run,
+  // and after a search it keeps each candidate's final state (done or paused)
+  #[serde(default)]
+  search_progress: Vec<JobU8>,
+  // The synchronization primitive other threads park on
+  // Skipped by serde (no meaningful bytes); deserialization installs a
+  // ready default, and resume then reseeds it from the `trained` snapshot
+  // above so the live state agrees with the checkpoint
+  #[serde(skip, default = "JobSignal::make")]
+  signal: Arc<JobSignal>
+}
+
+// A Mutex + Condvar pair guarding a single TaskState
+// Threads awaiting a model park here instead of spinning on the field
+struct JobSignal {
+  // The authoritative, lock-guarded task state
+  state: Mutex<TaskState>,
+  // Waiters block on this and are woken when the state changes
+  cvar: Condvar
+}
+
+// Coordination helpers for JobSignal
+impl JobSignal {
+  // Build a fresh signal that starts ready, wrapped for sharing
+  // Used as the serde default; resume immediately reseeds it from the
+  // checkpoint's `trained` snapshot via JobSignal::with_state
+  fn make() -> Arc<JobSignal> {
+    JobSignal::with_state(TaskState::ready) // Nothing running yet
+  }
+
+  // Build a signal seeded with a specific state, wrapped for sharing
+  // Lets resume align the live primitive with the deserialized `trained`
+  fn with_state(state: TaskState) -> Arc<JobSignal> {
+    Arc::new(JobSignal {
+      state: Mutex::new(state), // Start at the requested state
+      cvar: Condvar::new() // No waiters yet
+    })
+  }
+
+  // Set the current state and wake every parked waiter
+  // Called by the trainer whenever it advances the state machine
+  fn set(&self, new_state: TaskState) {
+    // Take the lock, overwrite the state, then notify
+    let mut guard = self.state.lock().unwrap(); // Acquire the lock
+    *guard = new_state; // Store the new task state
+    self.cvar.notify_all(); // Wake anything blocked in wait_until_idle
+  }
+
+  // Read the current state without blocking on the trainer
+  fn peek(&self) -> TaskState {
+    *self.state.lock().unwrap() // Copy the guarded TaskState out
+  }
+
+  // Request a cooperative stop between iterations
+  // Only a pending run can be paused; the trainer observes this at the top
+  // of its loop and exits cleanly. Returns whether the flip happened
+  fn request_pause(&self) -> bool {
+    // Take the lock and only downgrade an in-flight run to paused
+    let mut guard = self.state.lock().unwrap(); // Acquire the lock
+    if *guard == TaskState::pending {
+      *guard = TaskState::paused; // Ask the trainer to stop
+      self.cvar.notify_all(); // Wake any waiters parked on idle
+      true // A pause was requested
+    } else {
+      false // Nothing running to pause
+    }
+  }
+
+  // Park the calling thread until the state is no longer pending
+  // This replaces `while self.trained == TaskState::pending {}`
+  fn wait_until_idle(&self) {
+    // Grab the lock and sleep on the condvar while still pending
+    let mut guard = self.state.lock().unwrap(); // Acquire the lock
+    while *guard == TaskState::pending {
+      // Atomically release the lock and park until notify_all wakes us
+      guard = self.cvar.wait(guard).unwrap();
+    }
+    // Lock dropped here; state is now ready/done/paused
+  }
 }
 
 // Constructor impl block, see 
 impl SCKMModelConstructor for SCKM {
   // The constructor
-  fn new(given_data: Vec<LabelBoolPoint>) -> self {
+  fn new(given_data: Vec<LabelBoolPoint>, metric: MetricKind) -> self {
     // Build the SCKM object
     SCKM {
       data: given_data // Use given data
       result: intial_result, // Use generated cluster IDs
-      trained: TaskState::ready // Ready to train
+      num_centers: JobU8::make(), // No center count discovered yet
+      iteration: 0_u32, // Fresh model starts at iteration zero
+      metric: metric, // Distance metric fixed for the model's lifetime
+      trained: TaskState::ready, // Ready to train
+      search_progress: Vec::new(), // No candidate-k search running yet
+      signal: JobSignal::make() // Live coordination primitive, starts ready
     }
   }
 }
@@ -66,81 +189,592 @@ impl SCKMModelConstructor for SCKM {
 // Trait functions, see SCKMModel
 impl SCKMModel for SCKM {
   // The train function, see SCKMModel
-  fn train(&mut self, eta: u32) -> option<Trained> {
-    // Check that self.trained is TaskState::ready
-    if self.trained != TaskState::ready {
-      return None // Return None
+  fn train(&mut self, eta: u32) -> Result<Trained, SckmError> {
+    // A completed model must not be silently retrained
+    if self.trained == TaskState::done {
+      return Err(SckmError::AlreadyTrained) // Already has centers
     }
-    // Set that the train task is pending
-    self.trained = TaskState::pending;
-    // Reset the JobU8 num_centers to 0 and mark as pending
-    self.num_centers = JobU8 {
-      num: 0_u8,
-      job: TaskState::pending
+    // Accept a fresh model (ready) or a resumed one (paused)
+    // Anything else (pending) is not a legal start point
+    if self.trained != TaskState::ready && self.trained != TaskState::paused {
+      return Err(SckmError::NotReady) // State wasn't ready
     }
+    // Cannot cluster an empty corpus
+    if self.data.is_empty() {
+      return Err(SckmError::EmptyData) // Nothing to train on
+    }
+    // A fresh cold train resets counters; a resume keeps them intact
+    if self.trained == TaskState::ready {
+      // Reset the JobU8 num_centers to 0 and mark as pending
+      self.num_centers = JobU8 {
+        num: 0_u8,
+        job: TaskState::pending
+      }
+      // Restart the iteration counter for a fresh cold train
+      self.iteration = 0_u32;
+    }
+    // Set that the train task is pending (for both fresh and resumed)
+    // Mirror it into the live signal so late-arriving waiters park
+    self.trained = TaskState::pending;
+    self.signal.set(TaskState::pending);
     // Iterate until deemed complete by SCKM::training_iteration
-    while self.trained == TaskState::pending {
+    // Re-read the live signal each pass so a caller on another thread can
+    // request a stop by flipping it to paused between iterations
+    while self.signal.peek() == TaskState::pending {
       // Need to pass on eta, it is not a property
       self.training_iteration(eta: u32); // Call SCKM::training_iteration
     };
-    // Return Some of the Trained unit struct, to represent completion
+    // Fold the live state back into the serialized snapshot field
+    self.trained = self.signal.peek();
+    // If the loop exited because a caller requested a pause, training is
+    // not complete; the model is checkpointable and train can be re-entered
+    if self.trained == TaskState::paused {
+      return Err(SckmError::NotTrained) // Resume later to continue
+    }
+    // Return Ok of the Trained unit struct, to represent completion
     // Note that the SCKM::trained property is set to TaskState::done
     // This is done by the SCKM::training_iteration method
-    return Some(Trained)
+    return Ok(Trained)
   }
-  
+
+  // The train_search function, see SCKMModel
+  // Parallel candidate-k entry point; delegates to the internal worker
+  fn train_search(&mut self, eta: u32) -> Result<Trained, SckmError> {
+    self.run_candidate_search(eta) // Run the task-first center-count search
+  }
+
   // The same_cluster function, see SCKMModel
-  fn same_cluster(&self, a: Vec<bool>, b: Vec<bool>) -> option<ConnectEnum> {
+  fn same_cluster(&self, a: Vec<bool>, b: Vec<bool>) -> Result<ConnectEnum, SckmError> {
+    // A query only makes sense once training has completed
+    if self.trained != TaskState::done {
+      return Err(SckmError::NotTrained) // No centers to compare against yet
+    }
+    // The training dimension is the width of the first training point
+    // A done model with no data (e.g. a crafted/corrupt resumed checkpoint
+    // that bypassed train's EmptyData guard) has no dimension to check, so
+    // bail out with an actionable error rather than indexing into nothing
+    let expected = match self.data.first() {
+      Some(first) => first.data.point.len(), // Boolean vector width
+      None => return Err(SckmError::EmptyData) // No training points at all
+    };
+    // Both query points must match that dimension
+    if a.len() != expected {
+      return Err(SckmError::DimensionMismatch { expected, got: a.len() })
+    }
+    if b.len() != expected {
+      return Err(SckmError::DimensionMismatch { expected, got: b.len() })
+    }
     // Unwrap the Vec<option<BoolPoint>> result, which represents centers
-    let raw_cluster_centers = self.result // The wrapped cluster centers
-      .iter() // Not parallel, that causes issues with panic::catch_unwind
-      .map(|x| {(*x) // Deference the BoolPoint struct
-        .clone() // Clone it to avoid pointer collsion
-        .unwrap() // Convert E: option<BoolPoint> to E: BoolPoint
-      })
-      .collect::<Vec<BoolPoint>>(); // Collect the par_iter into a Vec
-    // Use CenterBasedClustering to check for same cluster
-    CenterBasedClustering::same_cluster(a, b, raw_cluster_centers)
+    // Rather than unwrap() (which panics on a still-None center), bail out
+    // with NotTrained if any center has not been discovered yet
+    let mut raw_cluster_centers = Vec::<BoolPoint>::new(); // Collected centers
+    for x in self.result.iter() { // Not parallel, keeps panic handling simple
+      match (*x).clone() { // Clone to avoid pointer collision
+        Some(center) => raw_cluster_centers.push(center), // A found center
+        None => return Err(SckmError::NotTrained) // Still incomplete
+      }
+    }
+    // Use CenterBasedClustering to check for same cluster, driving the
+    // nearest-center test with this model's metric so training and querying
+    // stay consistent
+    Ok(CenterBasedClustering::same_cluster(a, b, raw_cluster_centers, self.metric))
   }
   
   // The update_data function, see SCKMModel
+  // Blocking: parks the calling thread on the condvar until the trainer
+  // signals that it is no longer pending, then performs the cold rebuild
   fn update_data(&mut self, newdata: Vec<LabelBoolPoint>) {
-    // Await the self.trained TaskState to be not pending
-    while self.trained == TaskState::pending {};
-    // Set the self.trained TaskState to be pending
+    // Park efficiently instead of busy-waiting on self.trained
+    self.signal.wait_until_idle();
+    // We now hold logical ownership of an idle model; apply the rebuild
+    self.apply_cold_update(newdata);
+    // Implicitly return unit
+  }
+
+  // Non-blocking variant of update_data
+  // Returns immediately with Err(current state) if training is in flight,
+  // so callers that must not block can react rather than park
+  fn try_update_data(&mut self, newdata: Vec<LabelBoolPoint>) -> Result<(), TaskState> {
+    // Peek the live state without sleeping on the condvar
+    let current = self.signal.peek();
+    // Refuse if a training run is currently pending
+    if current == TaskState::pending {
+      return Err(current) // Report the in-flight state to the caller
+    }
+    // Idle: safe to perform the cold rebuild right now
+    self.apply_cold_update(newdata);
+    Ok(()) // Rebuild applied successfully
+  }
+
+  // The update_data_incremental function, see SCKMModel
+  // Near-online update that preserves the existing centers
+  fn update_data_incremental(&mut self, new_data: Vec<LabelBoolPoint>, eta: u32) {
+    // Park efficiently until any in-flight training finishes
+    self.signal.wait_until_idle();
+    // With no learned centers yet there is nothing to warm-start from, so
+    // fall back to the cold rebuild, which trains nothing and leaves the
+    // model ready for a fresh train over the combined data
+    if self.trained != TaskState::done {
+      // Combine the existing corpus with the new points, then cold rebuild
+      let mut combined = std::mem::take(&mut self.data); // Take the old data
+      combined.extend(new_data); // Append the new points
+      self.apply_cold_update(combined); // Cold rebuild over the combined data
+      return; // Nothing more to warm-start
+    }
+    // Announce the incremental run across snapshot and signal
     self.trained = TaskState::pending;
+    self.signal.set(TaskState::pending);
+    // Start from the centers we already learned
+    let mut centers = self.result // The learned centers
+      .iter() // Walk them
+      .filter_map(|x| (*x).clone()) // Drop any still-None slots
+      .collect::<Vec<BoolPoint>>(); // Collect the initialization
+    // A new point farther than this from every center (under the model's
+    // metric) is poorly covered and earns its own center; the radius is
+    // scaled to the metric's units so it is not metric-blind
+    let coverage = self.coverage_radius(eta);
+    // Walk the new points, absorbing the well-covered ones and collecting a
+    // fresh candidate center for any that no existing center covers
+    let mut spawned = Vec::<BoolPoint>::new(); // Centers proposed by new points
+    for lbp in new_data.iter() {
+      // Distance to the nearest existing center
+      let nearest = centers // The warm-start centers
+        .iter() // Walk them
+        .map(|c| self.metric_distance(&lbp.data.point, &c.point)) // Metric to each
+        .fold(f64::INFINITY, f64::min); // Closest center distance
+      if nearest > coverage {
+        spawned.push(lbp.data.clone()); // Poorly covered: propose a center
+      }
+      // Otherwise the point is well covered and simply absorbed
+    }
+    // Split/merge touches ONLY the newly spawned centers: the warm-start
+    // initialization is never dropped, so a feed where every new point is
+    // well covered adds no center (the settling steps below may still move
+    // the existing ones). A spawned center
+    // is kept only if it is not within coverage of a center already in the
+    // set (an existing center or an earlier-kept spawn)
+    for c in spawned.into_iter() { // Consider each proposed center
+      let covered = centers // Centers already in the set
+        .iter() // Walk them
+        .any(|m| self.metric_distance(&m.point, &c.point) <= coverage); // Within radius?
+      if !covered {
+        centers.push(c); // Genuinely new region: keep the spawned center
+      }
+    }
+    // Fold the new points into the corpus for the settling pass
+    self.data.extend(new_data);
+    // Install the warm-started centers as the current result
+    self.result = centers // The warm-started centers
+      .into_iter() // Consume them
+      .map(Some) // Wrap each in Some
+      .collect::<Vec<option<BoolPoint>>>(); // Into the result vector
+    // Record the provisional count while we settle
+    self.num_centers = JobU8::new(Some(self.result.len() as u8), TaskState::pending);
+    // Run a bounded number of mini-batch steps to let the centers settle
+    // around the combined data, rather than iterating to full convergence
+    let max_steps = 8_u32; // Mini-batch budget for an incremental update
+    let mut step = 0_u32; // Steps taken so far
+    while step < max_steps && self.signal.peek() == TaskState::pending {
+      self.training_iteration(eta); // One pure settling step
+      step += 1_u32; // Count it
+    }
+    // Finalize: mark done and wake any parked waiters
+    self.num_centers = JobU8::new(Some(self.result.len() as u8), TaskState::done);
+    self.trained = TaskState::done;
+    self.signal.set(TaskState::done);
+  }
+}
+
+// Internal training methods, see SCKM::train
+impl SCKM {
+  // Shared body of update_data / try_update_data: mark pending, rebuild
+  // from scratch via SCKM::new, then signal ready so waiters wake
+  fn apply_cold_update(&mut self, newdata: Vec<LabelBoolPoint>) {
+    // Set the task state to pending across both snapshot and signal
+    self.trained = TaskState::pending;
+    self.signal.set(TaskState::pending);
     // Generate a new SCKM object with the desired fields
-    let new_SCKM_object = SCKM::new(newdata);
+    // Preserve this model's metric so the rebuild keeps the same geometry
+    let new_SCKM_object = SCKM::new(newdata, self.metric);
     // Update the data field
     self.data = new_SCKM_object.data;
     // Update the result field
     self.result = new_SCKM_object.result;
-    // Set self.trained TaskState to ready
+    // Set the task state to ready and wake any parked waiters
     self.trained = TaskState::ready;
-    // Implicitly return unit
+    self.signal.set(TaskState::ready);
+  }
+
+  // Task-first parallel search over candidate center counts
+  // Instead of discovering num_centers sequentially (as SCKM::train does),
+  // this builds a queue of candidate-k tasks and dispatches them across the
+  // rayon pool so idle workers pull the next untried k rather than owning a
+  // fixed slice. The best-scoring k wins and its centers populate result.
+  // Private worker; reached through the SCKMModel::train_search entry point.
+  fn run_candidate_search(&mut self, eta: u32) -> Result<Trained, SckmError> {
+    // Entry guards mirror SCKM::train exactly
+    if self.trained == TaskState::done {
+      return Err(SckmError::AlreadyTrained) // Already has centers
+    }
+    if self.trained != TaskState::ready && self.trained != TaskState::paused {
+      return Err(SckmError::NotReady) // State wasn't ready
+    }
+    if self.data.is_empty() {
+      return Err(SckmError::EmptyData) // Nothing to cluster
+    }
+    // Announce that a run is in flight across snapshot and signal
+    self.trained = TaskState::pending;
+    self.signal.set(TaskState::pending);
+    // Derive k_max from eta and the corpus size: a larger eta penalizes
+    // extra centers, so fewer candidate counts are worth evaluating
+    let k_max = (self.data.len() / (eta as usize + 1)).max(1);
+    // Build the queue of candidate-k tasks, k = 1..=k_max
+    let candidates = (1..=k_max).collect::<Vec<usize>>();
+    // Seed observable per-k progress, one JobU8 per candidate, all pending
+    self.search_progress = candidates // One entry per candidate k
+      .iter() // Walk the candidate list
+      .map(|&k| JobU8::new(Some(k as u8), TaskState::pending)) // k, still pending
+      .collect::<Vec<JobU8>>(); // Collect into the progress vector
+    // Interior-mutable mirror the workers write their own slot into, since
+    // &mut self cannot cross the par_iter boundary; folded back in afterwards
+    let progress = Arc::new(candidates // One locked slot per candidate k
+      .iter() // Walk the candidate list
+      .map(|&k| Mutex::new(JobU8::new(Some(k as u8), TaskState::pending))) // Pending slot
+      .collect::<Vec<Mutex<JobU8>>>()); // Collect the slot vector
+    // Cooperative cancellation threshold shared across workers: once a
+    // candidate reaches zero within-cluster cost at some k0, any k strictly
+    // larger than k0 can only add eta*k penalty and so cannot win, but
+    // smaller k (lower penalty, possibly also zero cost) must still run.
+    // Starts at usize::MAX so nothing is cancelled until a zero-cost k lands
+    let cancel_above = Arc::new(AtomicUsize::new(usize::MAX));
+    // Shared best result so far: (objective score, k, centers)
+    let best = Arc::new(Mutex::new(None)); // None until the first task lands
+    // Dispatch task-first: par_iter hands the next k to whichever worker
+    // is idle, rather than pre-slicing the range per thread
+    candidates.par_iter().for_each(|&k| {
+      // Cooperative cancellation: a strictly-smaller k already achieved zero
+      // cost, so this larger k cannot beat it; skip only in that case
+      if k > cancel_above.load(Ordering::Relaxed) {
+        // Record that this slot was abandoned rather than left pending
+        *progress[k - 1].lock().unwrap() = JobU8::new(Some(k as u8), TaskState::paused);
+        return; // Abandon this task, a smaller k already wins on penalty
+      }
+      // Score this candidate k (seed centers plus one assignment pass)
+      let (centers, within_cost) = self.cluster_for_k(k);
+      // Objective: within-cluster cost under the model's metric, penalized by eta * k
+      let score = within_cost + (eta as f64) * (k as f64);
+      // Fold this task's score into the shared best under the lock
+      let mut guard = best.lock().unwrap(); // Acquire the best slot
+      let improved = match *guard { // Compare against the incumbent
+        None => true, // First task always wins
+        Some((incumbent_score, _, _)) => score < incumbent_score // Strictly better
+      };
+      if improved {
+        *guard = Some((score, k, centers)); // Record the new best
+      }
+      drop(guard); // Release before the cancellation check
+      // This candidate has finished; mark its slot done. The slots are local
+      // to this search and reach self.search_progress only once every
+      // candidate has finished or been abandoned
+      *progress[k - 1].lock().unwrap() = JobU8::new(Some(k as u8), TaskState::done);
+      // A zero within-cluster cost cannot be beaten on the cost term, and
+      // more centers only add penalty, so no larger k can win: wave off any
+      // candidate above this k, keeping the threshold at the smallest such k
+      if within_cost == 0.0_f64 {
+        cancel_above.fetch_min(k, Ordering::Relaxed); // Lower the cutoff to k
+      }
+    });
+    // Fold the finished per-k slots back into the observable progress field
+    self.search_progress = progress // The locked slots
+      .iter() // Walk them in candidate order
+      .map(|slot| *slot.lock().unwrap()) // Copy each JobU8 out
+      .collect::<Vec<JobU8>>(); // Into the observable progress vector
+    // The winning k and its centers populate the model
+    let winner = (*best.lock().unwrap()).clone(); // Take the best slot out
+    match winner {
+      Some((_, k, centers)) => {
+        // Wrap the winning centers back into the result vector
+        self.result = centers // The discovered centers
+          .into_iter() // Consume them
+          .map(Some) // Each becomes a Some(BoolPoint)
+          .collect::<Vec<option<BoolPoint>>>(); // Into the result vector
+        // Record the discovered count as a completed job
+        self.num_centers = JobU8::new(Some(k as u8), TaskState::done);
+        // Flip to done and wake any parked waiters
+        self.trained = TaskState::done;
+        self.signal.set(TaskState::done);
+        Ok(Trained) // Search succeeded
+      },
+      None => {
+        // No candidate produced a result; return to a ready state
+        self.trained = TaskState::ready;
+        self.signal.set(TaskState::ready);
+        Err(SckmError::EmptyData) // Nothing usable was found
+      }
+    }
+  }
+
+  // Score a single candidate center count k: seeds k centers and runs one
+  // assignment pass; the seeds are not refined by any recentering pass
+  // Returns the seed centers and the total within-cluster cost under the
+  // model's metric
+  fn cluster_for_k(&self, k: usize) -> (Vec<BoolPoint>, f64) {
+    // Seed k centers from the first k data points; deterministic seeding
+    // keeps a given k reproducible across runs and resumes
+    let centers = self.data // The training corpus
+      .iter() // Walk the points
+      .take(k) // Take the first k as initial centers
+      .map(|lbp| lbp.data.clone()) // Pull out each BoolPoint
+      .collect::<Vec<BoolPoint>>(); // Collect the seed centers
+    // Single assignment pass: each point joins its nearest center
+    let mut total_cost = 0.0_f64; // Accumulated within-cluster cost
+    for lbp in self.data.iter() { // Every training point
+      let mut best = f64::INFINITY; // Nearest-center distance for this point
+      for c in centers.iter() { // Compare against each center
+        let d = self.metric_distance(&lbp.data.point, &c.point); // Chosen metric
+        if d < best {
+          best = d; // Tighten the nearest-center distance
+        }
+      }
+      total_cost += best; // Add this point's contribution
+    }
+    (centers, total_cost) // Hand back centers and their cost
   }
-}
 
-// Internal training methods, see SCKM::train
-impl SCKM {
   // Training iteration, called by SCKM::train, an impl of SCKMModel trait
+  // This is a pure step: it writes only self.result, self.num_centers and
+  // self.iteration, plus self.trained and the live signal (set to done)
+  // on the step that converges. Center selection is fully
+  // deterministic (fixed seeding plus majority-vote recentering), so a
+  // model checkpointed mid-training resumes to byte-identical next steps
+  // without needing any captured RNG state
   fn training_iteration(&mut self, eta: u32) {
-    // TODO
+    // Pull the current centers out of the result slots
+    let centers = self.result // The current (possibly partial) centers
+      .iter() // Walk the slots
+      .filter_map(|x| (*x).clone()) // Drop any still-None slots
+      .collect::<Vec<BoolPoint>>(); // Collect the live centers
+    // With no centers seeded there is nothing to refine this step
+    if centers.is_empty() {
+      self.iteration += 1_u32; // Still count the (no-op) step
+      return; // Nothing to assign against yet
+    }
+    // The boolean dimension is the width of the first center
+    let dim = centers[0].point.len(); // Feature-vector width
+    // Per-cluster tallies for the majority-vote recentering
+    let mut sums = centers.iter().map(|_| vec![0_usize; dim]).collect::<Vec<Vec<usize>>>();
+    let mut counts = centers.iter().map(|_| 0_usize).collect::<Vec<usize>>();
+    // Lloyd assignment pass: each point joins its nearest center
+    for lbp in self.data.iter() { // Every training point
+      let mut best_i = 0_usize; // Index of the nearest center
+      let mut best_d = f64::INFINITY; // Distance to it
+      for (i, c) in centers.iter().enumerate() { // Compare against each center
+        let d = self.metric_distance(&lbp.data.point, &c.point); // Nearest-center test
+        if d < best_d {
+          best_d = d; // Tighten the best distance
+          best_i = i; // Remember the winning center
+        }
+      }
+      counts[best_i] += 1_usize; // One more point in that cluster
+      for (j, bit) in lbp.data.point.iter().enumerate() { // Tally set bits
+        if *bit {
+          sums[best_i][j] += 1_usize; // Count a set bit for dimension j
+        }
+      }
+    }
+    // Recompute each center as the per-dimension majority vote of its members
+    let mut new_centers = centers.clone(); // Start from the current centers
+    for i in 0..centers.len() { // Each cluster
+      if counts[i] == 0_usize {
+        continue; // Keep an empty cluster's center unchanged
+      }
+      for j in 0..dim { // Each dimension
+        // A bit is set in the new center when at least half its members set it
+        new_centers[i].point[j] = sums[i][j] * 2 >= counts[i];
+      }
+    }
+    // The step has converged when no center moved
+    let converged = new_centers // The refined centers
+      .iter() // Walk them
+      .zip(centers.iter()) // Pair with the previous centers
+      .all(|(a, b)| a.point == b.point); // Every center unchanged?
+    // Write the refined centers back into the result slots
+    self.result = new_centers // The refined centers
+      .into_iter() // Consume them
+      .map(Some) // Wrap each in Some
+      .collect::<Vec<option<BoolPoint>>>(); // Into the result vector
+    // Refresh the discovered-count job, marking it done once converged
+    let count_state = if converged { TaskState::done } else { TaskState::pending };
+    self.num_centers = JobU8::new(Some(centers.len() as u8), count_state);
+    // Record that this step has run so a resume continues from here
+    self.iteration += 1_u32;
+    // On convergence flip the state to done and wake every parked waiter
+    if converged {
+      self.trained = TaskState::done; // Training is complete
+      self.signal.set(TaskState::done); // Notify wait_until_idle sleepers
+    }
+  }
+
+  // Request that an in-flight train stop between iterations
+  // Safe to call from another thread while train is looping: the trainer
+  // re-reads the live signal each pass and exits when it sees paused,
+  // leaving a resumable, checkpointable model. Returns whether a run was
+  // actually pending and has now been asked to stop
+  pub fn pause(&self) -> bool {
+    self.signal.request_pause() // Flip the live signal to paused
+  }
+
+  // Serialize the full model state into a compact msgpack checkpoint
+  // Captures data, result, num_centers, the iteration counter, the metric
+  // and trained, so resume reconstructs an identical model
+  fn checkpoint(&self) -> Vec<u8> {
+    // Encode self via rmp_serde; the derive above covers every field
+    rmp_serde::to_vec(self).unwrap() // Bytes ready to persist
+  }
+
+  // Reconstruct a model from a checkpoint produced by SCKM::checkpoint
+  // Returns None if the bytes do not decode, or if the checkpoint's metric
+  // disagrees with `expect` (resuming under a different metric would change
+  // the model's geometry, so it is rejected rather than silently accepted)
+  fn resume(bytes: Vec<u8>, expect: MetricKind) -> option<SCKM> {
+    // Decode the msgpack bytes back into the full model state
+    match rmp_serde::from_slice::<SCKM>(&bytes) {
+      Ok(mut model) => {
+        // Reject a metric mismatch between checkpoint and caller
+        if model.metric != expect {
+          return None // Incompatible metric, do not resume
+        }
+        // The skipped signal defaulted to ready; reseed it from the saved
+        // `trained` so a mid-training (pending/paused) resume is not mistaken
+        // for an idle model by update_data / try_update_data
+        model.signal = JobSignal::with_state(model.trained);
+        Some(model) // A well-formed, metric-consistent checkpoint
+      },
+      Err(_) => None // Corrupt or incompatible bytes
+    }
+  }
+
+  // Coverage radius for the warm-start update, expressed in the chosen
+  // metric's own units so the "poorly covered" test is meaningful:
+  // Hamming distances are raw bit counts, whereas Jaccard/Dice live in
+  // [0, 1], so eta must be normalized by the feature-vector width for them
+  fn coverage_radius(&self, eta: u32) -> f64 {
+    match self.metric { // Interpret eta against the active metric's scale
+      MetricKind::Hamming => eta as f64, // Raw count of disagreeing bits
+      MetricKind::Jaccard | MetricKind::Dice => {
+        // Width of the boolean space, taken from the first training point
+        let dim = self.data.get(0).map(|lbp| lbp.data.point.len()).unwrap_or(0);
+        if dim == 0 {
+          return 1.0_f64 // Degenerate: cover everything
+        }
+        // eta bits out of dim as a fraction, saturated into [0, 1]
+        (eta as f64 / dim as f64).min(1.0_f64)
+      }
+    }
+  }
+
+  // Dispatch the model's chosen metric to its BoolMetric implementation
+  // All distance computations (assignment and querying) route through here
+  fn metric_distance(&self, a: &[bool], b: &[bool]) -> f64 {
+    match self.metric { // Pick the implementation captured at new
+      MetricKind::Hamming => Hamming.distance(a, b), // Bit disagreements
+      MetricKind::Jaccard => Jaccard.distance(a, b), // Set dissimilarity
+      MetricKind::Dice => Dice.distance(a, b) // Tanimoto/Dice dissimilarity
+    }
   }
 }
 
 // Represent a potentially labeled point in boolean space
+#[derive(Serialize, Deserialize)]
 struct LabelBoolPoint {
   data: BoolPoint, // The point itself
   label: option<LabelEnum> // Some(LabelEnum) if labeles, otherwise None
 }
 
 // Represent a point in boolean space
+#[derive(Serialize, Deserialize)]
 struct BoolPoint {
   point: Vec<bool> // The point itself
 }
 
+// Hamming distance between two boolean vectors
+// Counts positions where the two points disagree
+fn hamming(a: &[bool], b: &[bool]) -> usize {
+  a.iter() // Walk the first point
+    .zip(b.iter()) // Pair with the second point
+    .filter(|(x, y)| x != y) // Keep disagreeing positions
+    .count() // Number of differing bits
+}
+
+// A pluggable distance over boolean feature vectors
+// Implementors give a dissimilarity in [0, inf); smaller means closer
+pub trait BoolMetric {
+  // Distance between two equal-length boolean points
+  fn distance(&self, a: &[bool], b: &[bool]) -> f64
+}
+
+// The set-overlap counts shared by Jaccard and Dice
+// Returns (intersection, a-set-bits, b-set-bits) over the two points
+fn bit_overlap(a: &[bool], b: &[bool]) -> (usize, usize, usize) {
+  let inter = a.iter().zip(b.iter()).filter(|(x, y)| **x && **y).count(); // Both set
+  let a_bits = a.iter().filter(|x| **x).count(); // Set bits in a
+  let b_bits = b.iter().filter(|x| **x).count(); // Set bits in b
+  (inter, a_bits, b_bits) // Hand the counts back
+}
+
+// Hamming metric: fraction-free count of disagreeing positions
+struct Hamming;
+impl BoolMetric for Hamming {
+  fn distance(&self, a: &[bool], b: &[bool]) -> f64 {
+    hamming(a, b) as f64 // Reuse the free Hamming helper
+  }
+}
+
+// Jaccard metric: 1 - |A and B| / |A or B| over set bits
+struct Jaccard;
+impl BoolMetric for Jaccard {
+  fn distance(&self, a: &[bool], b: &[bool]) -> f64 {
+    let (inter, a_bits, b_bits) = bit_overlap(a, b); // Shared overlap counts
+    let union = a_bits + b_bits - inter; // |A or B|
+    if union == 0 {
+      return 0.0_f64 // Two empty points are identical
+    }
+    1.0_f64 - (inter as f64) / (union as f64) // Jaccard dissimilarity
+  }
+}
+
+// Tanimoto/Dice metric: 1 - 2|A and B| / (|A| + |B|) over set bits
+struct Dice;
+impl BoolMetric for Dice {
+  fn distance(&self, a: &[bool], b: &[bool]) -> f64 {
+    let (inter, a_bits, b_bits) = bit_overlap(a, b); // Shared overlap counts
+    let denom = a_bits + b_bits; // |A| + |B|
+    if denom == 0 {
+      return 0.0_f64 // Two empty points are identical
+    }
+    1.0_f64 - (2.0_f64 * inter as f64) / (denom as f64) // Dice dissimilarity
+  }
+}
+
+// Identity of the chosen metric, stored in SCKM and in the checkpoint
+// Used to dispatch to a BoolMetric impl and to reject a mismatched resume
+#[derive(Serialize, Deserialize, PartialEq)]
+enum MetricKind {
+  Hamming, // Count of disagreeing positions
+  Jaccard, // Set dissimilarity
+  Dice // Tanimoto/Dice dissimilarity
+}
+
+// Implement copy and clone traits for MetricKind
+impl Copy for MetricKind {}
+impl Clone for MetricKind {
+  fn clone(&self) -> self {
+    *self // Just return the enum value itself
+  }
+}
+
 // Represent the labels used in SAMPLe
+#[derive(Serialize, Deserialize)]
 enum LabelEnum {
   malware, // Malicious packages
   accept // Acceptable packages
@@ -155,10 +789,12 @@ impl Clone for LabelEnum {
 }
 
 // Represent the state of a task
+#[derive(Serialize, Deserialize)]
 enum TaskState {
   done, // The task is complete
   ready, // The task has not been started
-  pending // The task is currently running
+  pending, // The task is currently running
+  paused // The task was stopped between iterations and can be resumed
 }
 
 // Implement copy and clone traits for TaskState
@@ -169,6 +805,24 @@ impl Clone for TaskState {
   }
 }
 
+// Typed failures surfaced by train and same_cluster
+// Modeled on a job-error taxonomy so callers get actionable results
+enum SckmError {
+  NotReady, // train was called when the state wasn't ready
+  AlreadyTrained, // train was called on an already-done model
+  EmptyData, // there are no points to cluster
+  DimensionMismatch { expected: usize, got: usize }, // query width wrong
+  NotTrained // same_cluster (or a paused train) before centers exist
+}
+
+// Implement copy and clone traits for SckmError
+impl Copy for SckmError {}
+impl Clone for SckmError {
+  fn clone(&self) -> self {
+    *self // Just return the enum value itself
+  }
+}
+
 // Represent connectivity
 enum ConnectEnum {
   linked, // The points are linked, in the same cluster
@@ -184,6 +838,7 @@ impl Clone for ConnectEnum {
 }
 
 // Represent a u8 associated with a job
+#[derive(Serialize, Deserialize)]
 struct JobU8 {
   num: option<u8> // The u8 number value
   job: TaskState // The state of the associated task